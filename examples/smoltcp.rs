@@ -105,8 +105,7 @@ fn main() -> ! {
     writeln!(serial, "enc26j60 initialized").unwrap();
 
     // PHY Wrapper
-    let mut buf = [0u8; 1024];
-    let mut eth = Phy::new(enc28j60, &mut buf);
+    let mut eth = Phy::new(enc28j60);
     writeln!(serial, "eth initialized").unwrap();
 
     // Ethernet interface
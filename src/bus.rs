@@ -0,0 +1,92 @@
+//! SPI transport used to talk to the chip
+//!
+//! The driver only ever needs "assert chip-select, shift some bytes,
+//! deassert chip-select" as one atomic transaction. [`Bus`] captures exactly
+//! that, so [`crate::Enc28j60`] can be generic over how chip-select is
+//! managed: [`SpiBus`] owns a dedicated NCS pin (the original, `embedded-hal`
+//! 0.2-style `SpiBus`/`OutputPin` pair), while [`SpiDeviceBus`] defers to an
+//! `embedded-hal` 1.0 `SpiDevice` that already manages a shared bus's
+//! chip-select for us, so this driver can coexist with other peripherals on
+//! the same SPI lines.
+
+use embedded_hal::blocking::spi::{Transfer, Write as SpiWrite};
+use embedded_hal::digital::v2::OutputPin;
+use eh1::spi::SpiDevice;
+
+/// One atomic "assert CS, shift bytes, deassert CS" transaction
+pub trait Bus {
+    /// Error type of the underlying transport
+    type Error;
+
+    /// Writes `out`, ignoring whatever comes back over MISO
+    fn write(&mut self, out: &[u8]) -> Result<(), Self::Error>;
+
+    /// Writes `buf`, replacing its contents with whatever comes back over
+    /// MISO
+    fn transfer(&mut self, buf: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// [`Bus`] built from a raw `embedded-hal` 0.2 SPI peripheral plus a
+/// dedicated NCS pin that this driver drives directly
+pub struct SpiBus<SPI, NCS> {
+    pub(crate) spi: SPI,
+    pub(crate) ncs: NCS,
+}
+
+impl<SPI, NCS> SpiBus<SPI, NCS> {
+    /// Releases the SPI peripheral and the NCS pin
+    pub fn free(self) -> (SPI, NCS) {
+        (self.spi, self.ncs)
+    }
+}
+
+impl<SPI, NCS, E> Bus for SpiBus<SPI, NCS>
+where
+    SPI: Transfer<u8, Error = E> + SpiWrite<u8, Error = E>,
+    NCS: OutputPin,
+{
+    type Error = E;
+
+    fn write(&mut self, out: &[u8]) -> Result<(), E> {
+        self.ncs.set_low().ok();
+        let r = self.spi.write(out);
+        self.ncs.set_high().ok();
+        r
+    }
+
+    fn transfer(&mut self, buf: &mut [u8]) -> Result<(), E> {
+        self.ncs.set_low().ok();
+        let r = self.spi.transfer(buf).map(|_| ());
+        self.ncs.set_high().ok();
+        r
+    }
+}
+
+/// [`Bus`] built from an `embedded-hal` 1.0 [`SpiDevice`], which manages
+/// chip-select (and arbitration with other devices on a shared bus) itself
+pub struct SpiDeviceBus<D> {
+    pub(crate) device: D,
+}
+
+impl<D> SpiDeviceBus<D> {
+    /// Releases the `SpiDevice`, letting the caller hand the bus to another
+    /// peripheral between polls of this one
+    pub fn free(self) -> D {
+        self.device
+    }
+}
+
+impl<D, E> Bus for SpiDeviceBus<D>
+where
+    D: SpiDevice<u8, Error = E>,
+{
+    type Error = E;
+
+    fn write(&mut self, out: &[u8]) -> Result<(), E> {
+        self.device.write(out)
+    }
+
+    fn transfer(&mut self, buf: &mut [u8]) -> Result<(), E> {
+        self.device.transfer_in_place(buf)
+    }
+}
@@ -0,0 +1,16 @@
+/// Error type
+#[derive(Debug)]
+pub enum Error<E> {
+    /// Error reported by the underlying SPI transport
+    Spi(E),
+    /// A status bit polled by the driver (`ESTAT.RXBUSY`, `ECON1.TXRTS` or
+    /// `ESTAT.CLKRDY` during power-down/power-up) did not reach the expected
+    /// state within the allowed number of reads
+    ResetTimeout,
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(e: E) -> Self {
+        Error::Spi(e)
+    }
+}
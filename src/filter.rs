@@ -0,0 +1,146 @@
+//! Hardware receive-frame filtering (`ERXFCON` and friends)
+//!
+//! Programming these lets the chip itself drop frames `smoltcp` would
+//! otherwise have to receive and discard, at the cost of zero host CPU time.
+
+/// Up to 64 bytes of pattern to match against a frame, starting `offset`
+/// bytes in, used by
+/// the pattern-match filter (`ERXFCON.PMEN`)
+///
+/// `mask` selects which of the first `offset + mask.len() * 8` bytes of the
+/// frame participate in the match (`EPMM0..EPMM7`); `checksum` is the 16-bit
+/// checksum (`EPMCSL`/`EPMCSH`) that the masked bytes, starting at `offset`
+/// (`EPMOL`/`EPMOH`) bytes into the frame, must sum to for the frame to
+/// pass.
+#[derive(Clone, Copy, Debug)]
+pub struct PatternMatch {
+    /// Bit mask selecting which bytes of the frame participate (`EPMM0..7`)
+    pub mask: [u8; 8],
+    /// Expected 16-bit checksum of the selected bytes
+    pub checksum: u16,
+    /// Byte offset into the frame where the match window starts
+    pub offset: u16,
+}
+
+/// Hardware receive-filter configuration, programmed into `ERXFCON`
+///
+/// Built with the `with_*` methods and applied with
+/// [`crate::Enc28j60::set_receive_filter`]. [`Enc28j60::new`] programs the
+/// `unicast` + `broadcast` + `crc_check` default before this API is ever
+/// touched, so start from [`ReceiveFilter::default`] and flip on what's
+/// needed.
+///
+/// [`Enc28j60::new`]: crate::Enc28j60::new
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReceiveFilter {
+    pub(crate) unicast: bool,
+    pub(crate) broadcast: bool,
+    pub(crate) multicast: bool,
+    pub(crate) hash_table: bool,
+    pub(crate) magic_packet: bool,
+    pub(crate) pattern_match: Option<PatternMatch>,
+    pub(crate) require_and: bool,
+    pub(crate) require_crc: bool,
+}
+
+impl ReceiveFilter {
+    /// Accept frames whose destination MAC matches `MAADR` (UCEN)
+    pub fn with_unicast(mut self, enable: bool) -> Self {
+        self.unicast = enable;
+        self
+    }
+
+    /// Accept the broadcast address `FF:FF:FF:FF:FF:FF` (BCEN)
+    pub fn with_broadcast(mut self, enable: bool) -> Self {
+        self.broadcast = enable;
+        self
+    }
+
+    /// Accept any frame with the multicast bit set in its destination (MCEN)
+    pub fn with_multicast(mut self, enable: bool) -> Self {
+        self.multicast = enable;
+        self
+    }
+
+    /// Accept frames whose destination hashes into the 64-bit hash table
+    /// programmed through [`crate::Enc28j60::hash_filter_insert`] (HTEN)
+    pub fn with_hash_table(mut self, enable: bool) -> Self {
+        self.hash_table = enable;
+        self
+    }
+
+    /// Accept Wake-on-LAN magic packets addressed to the station MAC (MPEN)
+    pub fn with_magic_packet(mut self, enable: bool) -> Self {
+        self.magic_packet = enable;
+        self
+    }
+
+    /// Require frames to also pass a pattern/checksum match (PMEN)
+    pub fn with_pattern_match(mut self, pattern: Option<PatternMatch>) -> Self {
+        self.pattern_match = pattern;
+        self
+    }
+
+    /// Require incoming frames to pass the hardware CRC check (CRCEN)
+    pub fn with_crc_check(mut self, enable: bool) -> Self {
+        self.require_crc = enable;
+        self
+    }
+
+    /// Combine the enabled filters with AND instead of the default OR
+    /// (ANDOR); e.g. unicast AND pattern-match instead of unicast OR
+    /// pattern-match
+    pub fn with_and(mut self, enable: bool) -> Self {
+        self.require_and = enable;
+        self
+    }
+}
+
+/// Standard Ethernet CRC-32 (poly `0xEDB8_8320`, reflected, inverted
+/// output) over `data`, as used by the hash-table filter's index derivation
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            if crc & 1 != 0 {
+                crc = (crc >> 1) ^ 0xedb8_8320;
+            } else {
+                crc >>= 1;
+            }
+        }
+    }
+    !crc
+}
+
+/// Index (0..64) of a destination MAC into the 64-bit hash table: bits
+/// 28:23 of its CRC-32, per the ENC28J60 data sheet's hash filter section
+pub(crate) fn hash_index(mac: &[u8; 6]) -> u8 {
+    ((crc32(mac) >> 23) & 0x3f) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_standard_check_value() {
+        // The well-known "123456789" check value for this CRC-32 variant
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn hash_index_is_six_bits() {
+        let mac = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55];
+        assert!(hash_index(&mac) < 64);
+    }
+
+    #[test]
+    fn hash_index_matches_mdns_multicast_mac() {
+        // The IPv4 all-mDNS-nodes multicast MAC; expected index computed
+        // out-of-band from its CRC-32 (0x7b23_2103), bits 28:23 of which
+        // are 0b110110 = 54.
+        let mac = [0x01, 0x00, 0x5e, 0x00, 0x00, 0xfb];
+        assert_eq!(hash_index(&mac), 54);
+    }
+}
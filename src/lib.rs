@@ -0,0 +1,801 @@
+//! A platform-agnostic driver for the ENC28J60 Ethernet controller
+//!
+//! The ENC28J60 is an SPI-attached 10BASE-T Ethernet controller with an
+//! 8 KB packet buffer shared between receive and transmit. This crate talks
+//! to it over any `embedded-hal` SPI implementation and exposes just enough
+//! of the register map to bring up an Ethernet link and move frames; see
+//! [`smoltcp_phy`] for a `smoltcp` `Device` built on top.
+//!
+//! # References
+//!
+//! - [Microchip ENC28J60 data sheet, DS39662](http://ww1.microchip.com/downloads/en/devicedoc/39662e.pdf)
+
+#![deny(missing_docs)]
+#![no_std]
+
+mod bus;
+mod error;
+mod filter;
+pub mod register;
+pub mod smoltcp_phy;
+
+pub use crate::bus::{Bus, SpiBus, SpiDeviceBus};
+pub use crate::filter::{PatternMatch, ReceiveFilter};
+
+use eh1::spi::SpiDevice;
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi::{Transfer, Write};
+use embedded_hal::digital::v2::{InputPin, OutputPin};
+use embedded_hal::spi::{Mode, Phase, Polarity};
+
+pub use crate::error::Error;
+use crate::register::{
+    bank0::{
+        EPKTCNT, ERDPTH, ERDPTL, ERXFCON, ERXNDH, ERXNDL, ERXRDPTH, ERXRDPTL, ERXSTH, ERXSTL,
+        ETXNDH, ETXNDL, ETXSTH, ETXSTL, EWRPTH, EWRPTL,
+    },
+    bank1::{
+        EHT0, EHT1, EHT2, EHT3, EHT4, EHT5, EHT6, EHT7, EPMCSH, EPMCSL, EPMM0, EPMM1, EPMM2, EPMM3,
+        EPMM4, EPMM5, EPMM6, EPMM7, EPMOH, EPMOL,
+    },
+    bank2::{
+        macon1, macon3, micmd, MACON1, MACON3, MAIPGH, MAIPGL, MAMXFLH, MAMXFLL, MICMD, MIREGADR,
+        MIRDH, MIRDL, MIWRH, MIWRL,
+    },
+    bank3::{mistat, MAADR1, MAADR2, MAADR3, MAADR4, MAADR5, MAADR6, MISTAT},
+    common::{econ1, econ2, eie, eir, estat, ECON1, ECON2, EIE, EIR, ESTAT},
+    Bank, Register,
+};
+
+/// Total buffer size, in bytes, of the packet memory
+const BUFFER_SIZE: u16 = 8 * 1024;
+
+/// Maximum number of register reads [`Enc28j60::poll_until`] will issue
+/// before giving up and reporting [`Error::ResetTimeout`]
+const POLL_ATTEMPTS: u32 = 10_000;
+
+/// SPI mode that should be used for this device (CPOL = 0, CPHA = 0)
+pub const MODE: Mode = Mode {
+    polarity: Polarity::IdleLow,
+    phase: Phase::CaptureOnFirstTransition,
+};
+
+/// Free-running pointer to nothing
+///
+/// Used for the `INT` pin when the interrupt line hasn't been wired up and
+/// the driver should be polled instead; also usable for `RESET` when the
+/// reset pin is tied to the host reset.
+pub struct Unconnected;
+
+impl InputPin for Unconnected {
+    type Error = core::convert::Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+}
+
+impl OutputPin for Unconnected {
+    type Error = core::convert::Infallible;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// SPI opcodes (data sheet, table 4-1)
+#[derive(Clone, Copy)]
+enum Opcode {
+    ReadControlRegister = 0b000_00000,
+    ReadBufferMemory = 0b001_11010,
+    WriteControlRegister = 0b010_00000,
+    WriteBufferMemory = 0b011_11010,
+    BitFieldSet = 0b100_00000,
+    BitFieldClear = 0b101_00000,
+    SystemResetCommand = 0b111_11111,
+}
+
+/// The set of events reported by [`Enc28j60::pending`]
+///
+/// The INT pin is active-low and level-held for as long as any enabled and
+/// unmasked condition in `EIR` is set; in particular it stays asserted while
+/// `EPKTCNT > 0`, so a caller must drain every packet reported by
+/// `pending_packets` (e.g. by looping `receive` until it is zero)
+/// before the line will deassert.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Events {
+    /// Number of packets currently sitting in the receive buffer (`EPKTCNT`)
+    pub pending_packets: u8,
+    /// The PHY link state changed; call `link_state` to see the new value
+    pub link_changed: bool,
+    /// The last transmit attempt completed, successfully or not
+    pub transmit_done: bool,
+    /// The last transmit attempt failed
+    pub transmit_error: bool,
+    /// A receive error occurred (e.g. the receive buffer is full)
+    pub receive_error: bool,
+    /// A Wake-on-LAN magic packet matched (`ERXFCON.MPEN`); see
+    /// [`crate::ReceiveFilter::with_magic_packet`]
+    pub wake_on_lan: bool,
+}
+
+impl Events {
+    /// Whether any event fired
+    pub fn any(&self) -> bool {
+        self.pending_packets != 0
+            || self.link_changed
+            || self.transmit_done
+            || self.transmit_error
+            || self.receive_error
+            || self.wake_on_lan
+    }
+}
+
+/// Interrupt sources that can be routed to the INT pin
+///
+/// Passed to [`Enc28j60::listen`]; set more than one field to enable more
+/// than one source. `PKTIE`/`PKTIF` tracks `EPKTCNT`, so packet-received notification
+/// is always implicitly available through `pending_packets`.
+#[derive(Clone, Copy)]
+pub struct InterruptSources {
+    /// Notify when a packet has been received (`EPKTCNT` becomes non-zero)
+    pub packet: bool,
+    /// Notify when the PHY link state changes
+    pub link: bool,
+    /// Notify when a transmission completes (success or failure)
+    pub transmit: bool,
+    /// Notify on transmit error
+    pub transmit_error: bool,
+    /// Notify on receive error
+    pub receive_error: bool,
+    /// Notify when a Wake-on-LAN magic packet matches (see
+    /// [`ReceiveFilter::with_magic_packet`])
+    pub wake_on_lan: bool,
+}
+
+impl InterruptSources {
+    fn mask(&self) -> u8 {
+        let mut mask = eie::INTIE;
+        if self.packet {
+            mask |= eie::PKTIE;
+        }
+        if self.link {
+            mask |= eie::LINKIE;
+        }
+        if self.transmit {
+            mask |= eie::TXIE;
+        }
+        if self.transmit_error {
+            mask |= eie::TXERIE;
+        }
+        if self.receive_error {
+            mask |= eie::RXERIE;
+        }
+        if self.wake_on_lan {
+            mask |= eie::WOLIE;
+        }
+        mask
+    }
+}
+
+/// ENC28J60 driver
+///
+/// Generic over the [`Bus`] used to reach the chip: [`SpiBus`] for a
+/// dedicated SPI peripheral plus NCS pin (built with [`Enc28j60::new`]), or
+/// [`SpiDeviceBus`] for a shared bus managed by an `embedded-hal` 1.0
+/// `SpiDevice` (built with [`Enc28j60::new_with_spi_device`]).
+pub struct Enc28j60<BUS, INT, RESET> {
+    bus: BUS,
+    int: INT,
+    reset: RESET,
+    bank: Bank,
+    next_packet: u16,
+    tx_start: u16,
+}
+
+impl<SPI, NCS, INT, RESET, E> Enc28j60<SpiBus<SPI, NCS>, INT, RESET>
+where
+    SPI: Transfer<u8, Error = E> + Write<u8, Error = E>,
+    NCS: OutputPin,
+    INT: InputPin,
+    RESET: OutputPin,
+{
+    /// Initializes the driver from a dedicated SPI peripheral and NCS pin
+    ///
+    /// `buffer_size` is the number of bytes, out of the chip's 8 KB of
+    /// packet memory, to reserve for the receive FIFO; the remainder is used
+    /// for transmission. `int` can be [`Unconnected`] if the caller intends
+    /// to poll instead of wiring up the INT pin to a GPIO interrupt.
+    pub fn new<D>(
+        spi: SPI,
+        ncs: NCS,
+        int: INT,
+        reset: RESET,
+        delay: &mut D,
+        buffer_size: u16,
+        mac_addr: [u8; 6],
+    ) -> Result<Self, Error<E>>
+    where
+        D: DelayMs<u8>,
+    {
+        Self::init(SpiBus { spi, ncs }, int, reset, delay, buffer_size, mac_addr)
+    }
+}
+
+impl<D, INT, RESET, E> Enc28j60<SpiDeviceBus<D>, INT, RESET>
+where
+    D: SpiDevice<u8, Error = E>,
+    INT: InputPin,
+    RESET: OutputPin,
+{
+    /// Initializes the driver from a shared bus, through an `embedded-hal`
+    /// 1.0 `SpiDevice` that already manages chip-select (and arbitration
+    /// with other peripherals on the bus) itself
+    ///
+    /// Every register and buffer-memory transaction acquires the device for
+    /// just that transaction, so the bus can be handed to another chip (for
+    /// example, an FPGA configuration flash sharing the same SPI lines)
+    /// between calls into this driver.
+    pub fn new_with_spi_device<Dl>(
+        device: D,
+        int: INT,
+        reset: RESET,
+        delay: &mut Dl,
+        buffer_size: u16,
+        mac_addr: [u8; 6],
+    ) -> Result<Self, Error<E>>
+    where
+        Dl: DelayMs<u8>,
+    {
+        Self::init(SpiDeviceBus { device }, int, reset, delay, buffer_size, mac_addr)
+    }
+}
+
+impl<BUS, INT, RESET, E> Enc28j60<BUS, INT, RESET>
+where
+    BUS: Bus<Error = E>,
+    INT: InputPin,
+    RESET: OutputPin,
+{
+    fn init<D>(
+        bus: BUS,
+        int: INT,
+        mut reset: RESET,
+        delay: &mut D,
+        buffer_size: u16,
+        mac_addr: [u8; 6],
+    ) -> Result<Self, Error<E>>
+    where
+        D: DelayMs<u8>,
+    {
+        let _ = reset.set_low();
+        delay.delay_ms(1);
+        let _ = reset.set_high();
+        delay.delay_ms(1);
+
+        let mut enc28j60 = Enc28j60 {
+            bus,
+            int,
+            reset,
+            bank: Bank::Bank0,
+            next_packet: 0,
+            tx_start: 0,
+        };
+
+        enc28j60.soft_reset()?;
+        delay.delay_ms(1);
+
+        let rx_end = buffer_size.saturating_sub(1).min(BUFFER_SIZE - 1);
+        enc28j60.next_packet = 0;
+        enc28j60.tx_start = rx_end.wrapping_add(1);
+        enc28j60.write_control_register(ERXSTL::ADDR, 0x00)?;
+        enc28j60.write_control_register(ERXSTH::ADDR, 0x00)?;
+        enc28j60.write_control_register(ERXRDPTL::ADDR, 0x00)?;
+        enc28j60.write_control_register(ERXRDPTH::ADDR, 0x00)?;
+        enc28j60.write_control_register(ERXNDL::ADDR, rx_end as u8)?;
+        enc28j60.write_control_register(ERXNDH::ADDR, (rx_end >> 8) as u8)?;
+        enc28j60.write_control_register(ETXSTL::ADDR, (rx_end.wrapping_add(1)) as u8)?;
+        enc28j60.write_control_register(ETXSTH::ADDR, ((rx_end.wrapping_add(1)) >> 8) as u8)?;
+        enc28j60.write_control_register(ETXNDL::ADDR, (BUFFER_SIZE - 1) as u8)?;
+        enc28j60.write_control_register(ETXNDH::ADDR, ((BUFFER_SIZE - 1) >> 8) as u8)?;
+
+        // Accept unicast and broadcast traffic by default
+        enc28j60.write_control_register(
+            ERXFCON::ADDR,
+            register::bank0::erxfcon::UCEN | register::bank0::erxfcon::BCEN | register::bank0::erxfcon::CRCEN,
+        )?;
+
+        enc28j60.write_control_register(MACON1::ADDR, macon1::MARXEN | macon1::TXPAUS | macon1::RXPAUS)?;
+        enc28j60.write_control_register(
+            MACON3::ADDR,
+            macon3::PADCFG0 | macon3::TXCRCEN | macon3::FRMLNEN | macon3::FULDPX,
+        )?;
+        enc28j60.write_control_register(MAIPGL::ADDR, 0x12)?;
+        enc28j60.write_control_register(MAIPGH::ADDR, 0x0c)?;
+        enc28j60.write_control_register(MAMXFLL::ADDR, 0xee)?;
+        enc28j60.write_control_register(MAMXFLH::ADDR, 0x05)?;
+
+        enc28j60.write_control_register(MAADR1::ADDR, mac_addr[0])?;
+        enc28j60.write_control_register(MAADR2::ADDR, mac_addr[1])?;
+        enc28j60.write_control_register(MAADR3::ADDR, mac_addr[2])?;
+        enc28j60.write_control_register(MAADR4::ADDR, mac_addr[3])?;
+        enc28j60.write_control_register(MAADR5::ADDR, mac_addr[4])?;
+        enc28j60.write_control_register(MAADR6::ADDR, mac_addr[5])?;
+
+        enc28j60.bit_field_set(ECON1::ADDR, econ1::RXEN)?;
+
+        Ok(enc28j60)
+    }
+
+    /// Configures which interrupt sources are routed to the (active-low)
+    /// INT pin, by programming `EIE`
+    ///
+    /// Passing `InterruptSources { packet: false, .. }` still leaves
+    /// `EPKTCNT` readable through [`Enc28j60::pending`]; it only controls
+    /// whether a new packet asserts the INT pin on its own.
+    pub fn listen(&mut self, sources: InterruptSources) -> Result<(), Error<E>> {
+        if sources.link {
+            // PHIE (PHY register 0x12): PLNKIE | PGEIE, which LINKIF also
+            // depends on; writing MIWRH starts the MII write
+            self.write_control_register(MIREGADR::ADDR, 0x12)?;
+            self.write_control_register(MIWRL::ADDR, (1 << 4) | (1 << 1))?;
+            self.write_control_register(MIWRH::ADDR, 0)?;
+            while self.read_control_register(MISTAT::ADDR)? & mistat::BUSY != 0 {}
+        }
+        self.write_control_register(EIE::ADDR, sources.mask())
+    }
+
+    /// Disables all interrupt sources, un-asserting the INT pin
+    pub fn unlisten_all(&mut self) -> Result<(), Error<E>> {
+        self.write_control_register(EIE::ADDR, 0)
+    }
+
+    /// Reads `EIR` and `EPKTCNT` to report what caused the INT pin to assert
+    ///
+    /// This does not clear anything; call [`Enc28j60::ack`] with the events
+    /// that have been handled once the caller is done with them. Because the
+    /// INT pin is level-held while `EPKTCNT > 0`, `pending_packets` must be
+    /// drained down to zero (by reading out that many packets) for the line
+    /// to deassert, regardless of `ack`.
+    pub fn pending(&mut self) -> Result<Events, Error<E>> {
+        let eir = self.read_control_register(EIR::ADDR)?;
+        let pktcnt = self.read_control_register(register::bank0::EPKTCNT::ADDR)?;
+
+        Ok(Events {
+            pending_packets: pktcnt,
+            link_changed: eir & eir::LINKIF != 0,
+            transmit_done: eir & eir::TXIF != 0,
+            transmit_error: eir & eir::TXERIF != 0,
+            receive_error: eir & eir::RXERIF != 0,
+            wake_on_lan: eir & eir::WOLIF != 0,
+        })
+    }
+
+    /// Clears the latching `EIR` flags for the events the caller has handled
+    ///
+    /// `events.pending_packets` is ignored: `EPKTCNT` is decremented
+    /// separately, by [`Enc28j60::ack_packet`], once per packet actually
+    /// pulled out of the receive FIFO.
+    pub fn ack(&mut self, events: Events) -> Result<(), Error<E>> {
+        let mut mask = 0;
+        if events.link_changed {
+            // LINKIF is not cleared by Bit Field Clear; reading PHIR clears it
+            self.read_phy_register(0x13)?;
+        }
+        if events.transmit_done {
+            mask |= eir::TXIF;
+        }
+        if events.transmit_error {
+            mask |= eir::TXERIF;
+        }
+        if events.receive_error {
+            mask |= eir::RXERIF;
+        }
+        if events.wake_on_lan {
+            mask |= eir::WOLIF;
+        }
+        if mask != 0 {
+            self.bit_field_clear(EIR::ADDR, mask)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the current level of the INT pin
+    ///
+    /// The line is active-low, so `true` means some enabled and
+    /// unmasked condition in `EIR` (or a non-zero `EPKTCNT`) is asserted.
+    /// Useful to disambiguate a shared EXTI line or to poll without wiring
+    /// up an interrupt at all.
+    pub fn interrupt_is_asserted(&mut self) -> bool {
+        self.int.is_low().unwrap_or(false)
+    }
+
+    /// Decrements `EPKTCNT` by one
+    ///
+    /// [`Enc28j60::receive`] (which the [`smoltcp_phy`] tokens also use)
+    /// already calls this once for every packet it reads out of the receive
+    /// FIFO. `EPKTCNT` only reaches zero, and the INT pin only deasserts,
+    /// once this has been called for every queued packet.
+    pub fn ack_packet(&mut self) -> Result<(), Error<E>> {
+        self.bit_field_set(ECON2::ADDR, econ2::PKTDEC)
+    }
+
+    /// Programs `ERXFCON` (and, if set, the pattern-match registers) from a
+    /// [`ReceiveFilter`]
+    ///
+    /// This only changes which frames the chip admits into the receive
+    /// FIFO; it doesn't touch the hash table itself, so toggling
+    /// `with_hash_table` back on after clearing it still sees whatever was
+    /// last programmed with [`Enc28j60::hash_filter_insert`].
+    pub fn set_receive_filter(&mut self, filter: ReceiveFilter) -> Result<(), Error<E>> {
+        if let Some(pattern) = filter.pattern_match {
+            self.write_control_register(EPMM0::ADDR, pattern.mask[0])?;
+            self.write_control_register(EPMM1::ADDR, pattern.mask[1])?;
+            self.write_control_register(EPMM2::ADDR, pattern.mask[2])?;
+            self.write_control_register(EPMM3::ADDR, pattern.mask[3])?;
+            self.write_control_register(EPMM4::ADDR, pattern.mask[4])?;
+            self.write_control_register(EPMM5::ADDR, pattern.mask[5])?;
+            self.write_control_register(EPMM6::ADDR, pattern.mask[6])?;
+            self.write_control_register(EPMM7::ADDR, pattern.mask[7])?;
+            self.write_control_register(EPMCSL::ADDR, pattern.checksum as u8)?;
+            self.write_control_register(EPMCSH::ADDR, (pattern.checksum >> 8) as u8)?;
+            self.write_control_register(EPMOL::ADDR, pattern.offset as u8)?;
+            self.write_control_register(EPMOH::ADDR, (pattern.offset >> 8) as u8)?;
+        }
+
+        let mut mask = 0;
+        if filter.unicast {
+            mask |= register::bank0::erxfcon::UCEN;
+        }
+        if filter.broadcast {
+            mask |= register::bank0::erxfcon::BCEN;
+        }
+        if filter.multicast {
+            mask |= register::bank0::erxfcon::MCEN;
+        }
+        if filter.hash_table {
+            mask |= register::bank0::erxfcon::HTEN;
+        }
+        if filter.magic_packet {
+            mask |= register::bank0::erxfcon::MPEN;
+        }
+        if filter.pattern_match.is_some() {
+            mask |= register::bank0::erxfcon::PMEN;
+        }
+        if filter.require_and {
+            mask |= register::bank0::erxfcon::ANDOR;
+        }
+        if filter.require_crc {
+            mask |= register::bank0::erxfcon::CRCEN;
+        }
+        self.write_control_register(ERXFCON::ADDR, mask)
+    }
+
+    /// Subscribes to a multicast group by setting this `mac`'s bit in the
+    /// 64-bit hash table (`EHT0..EHT7`)
+    ///
+    /// Takes effect once [`ReceiveFilter::with_hash_table`] is enabled
+    /// through [`Enc28j60::set_receive_filter`]. Because the hash is lossy
+    /// (64 buckets for 2^48 possible addresses), a small number of
+    /// unwanted groups may alias into an inserted bucket and also pass.
+    pub fn hash_filter_insert(&mut self, mac: [u8; 6]) -> Result<(), Error<E>> {
+        let index = filter::hash_index(&mac);
+        let mask = 1 << (index % 8);
+        match index / 8 {
+            0 => self.bit_field_set(EHT0::ADDR, mask),
+            1 => self.bit_field_set(EHT1::ADDR, mask),
+            2 => self.bit_field_set(EHT2::ADDR, mask),
+            3 => self.bit_field_set(EHT3::ADDR, mask),
+            4 => self.bit_field_set(EHT4::ADDR, mask),
+            5 => self.bit_field_set(EHT5::ADDR, mask),
+            6 => self.bit_field_set(EHT6::ADDR, mask),
+            _ => self.bit_field_set(EHT7::ADDR, mask),
+        }
+    }
+
+    /// Clears the entire 64-bit hash table, removing every multicast
+    /// subscription added with [`Enc28j60::hash_filter_insert`]
+    pub fn hash_filter_clear(&mut self) -> Result<(), Error<E>> {
+        self.write_control_register(EHT0::ADDR, 0)?;
+        self.write_control_register(EHT1::ADDR, 0)?;
+        self.write_control_register(EHT2::ADDR, 0)?;
+        self.write_control_register(EHT3::ADDR, 0)?;
+        self.write_control_register(EHT4::ADDR, 0)?;
+        self.write_control_register(EHT5::ADDR, 0)?;
+        self.write_control_register(EHT6::ADDR, 0)?;
+        self.write_control_register(EHT7::ADDR, 0)
+    }
+
+    /// Puts the chip into low-power mode (`ECON2.PWRSV`)
+    ///
+    /// Follows the data sheet's power-down sequence: clear `ECON1.RXEN` to
+    /// stop reception, wait for `ESTAT.RXBUSY` to clear (a packet may
+    /// already be mid-receive), wait for `ECON1.TXRTS` to clear (ditto for
+    /// transmit), then set `PWRSV`. Reception stays disabled while powered
+    /// down, so the magic-packet filter
+    /// ([`ReceiveFilter::with_magic_packet`]) only matches frames received
+    /// before this call or after [`Enc28j60::power_up`].
+    ///
+    /// Set `disable_voltage_regulator` to also clear `ECON2.VRPS`, the
+    /// voltage regulator's power-save enable, before `PWRSV` is set.
+    ///
+    /// Returns [`Error::ResetTimeout`] if `RXBUSY`/`TXRTS` don't clear
+    /// within [`POLL_ATTEMPTS`] reads, rather than spinning forever on a
+    /// wedged chip.
+    pub fn power_down(&mut self, disable_voltage_regulator: bool) -> Result<(), Error<E>> {
+        self.bit_field_clear(ECON1::ADDR, econ1::RXEN)?;
+        self.poll_until(|s| Ok(s.read_control_register(ESTAT::ADDR)? & estat::RXBUSY == 0))?;
+        self.poll_until(|s| Ok(s.read_control_register(ECON1::ADDR)? & econ1::TXRTS == 0))?;
+
+        if disable_voltage_regulator {
+            self.bit_field_clear(ECON2::ADDR, econ2::VRPS)?;
+        }
+        self.bit_field_set(ECON2::ADDR, econ2::PWRSV)
+    }
+
+    /// Wakes the chip back up from [`Enc28j60::power_down`]
+    ///
+    /// Clears `ECON2.PWRSV`, waits for the internal oscillator to
+    /// stabilize (`ESTAT.CLKRDY`), then re-enables `ECON1.RXEN`.
+    ///
+    /// Returns [`Error::ResetTimeout`] if `CLKRDY` doesn't set within
+    /// [`POLL_ATTEMPTS`] reads, rather than spinning forever on a wedged
+    /// chip (e.g. a dead crystal).
+    pub fn power_up(&mut self) -> Result<(), Error<E>> {
+        self.bit_field_clear(ECON2::ADDR, econ2::PWRSV)?;
+        self.poll_until(|s| Ok(s.read_control_register(ESTAT::ADDR)? & estat::CLKRDY != 0))?;
+        self.bit_field_set(ECON1::ADDR, econ1::RXEN)
+    }
+
+    /// Polls `condition` until it reports `true`, up to [`POLL_ATTEMPTS`]
+    /// times, returning [`Error::ResetTimeout`] if it never does
+    fn poll_until(
+        &mut self,
+        mut condition: impl FnMut(&mut Self) -> Result<bool, Error<E>>,
+    ) -> Result<(), Error<E>> {
+        for _ in 0..POLL_ATTEMPTS {
+            if condition(self)? {
+                return Ok(());
+            }
+        }
+        Err(Error::ResetTimeout)
+    }
+
+    /// Reads `EPKTCNT`, the number of frames currently queued in the
+    /// receive FIFO, without otherwise touching the FIFO
+    ///
+    /// Used by [`crate::smoltcp_phy`] to decide whether a frame is
+    /// available before committing to the Read Buffer Memory transfer that
+    /// actually pulls it out.
+    pub(crate) fn packet_count(&mut self) -> Result<u8, Error<E>> {
+        self.read_control_register(EPKTCNT::ADDR)
+    }
+
+    /// Reads one queued packet into `buf`, returning its length in bytes
+    ///
+    /// Returns `0` without touching `buf` if `EPKTCNT` is zero. `buf` must be
+    /// at least as large as the largest frame the link partner may send;
+    /// frames that don't fit are truncated.
+    pub fn receive(&mut self, buf: &mut [u8]) -> Result<u16, Error<E>> {
+        if self.read_control_register(EPKTCNT::ADDR)? == 0 {
+            return Ok(0);
+        }
+
+        self.write_control_register(ERDPTL::ADDR, self.next_packet as u8)?;
+        self.write_control_register(ERDPTH::ADDR, (self.next_packet >> 8) as u8)?;
+
+        // Per-packet header: next packet pointer (2 bytes) + receive status
+        // vector (4 bytes), of which only the byte count (first 2 bytes) is used.
+        let mut header = [0u8; 6];
+        self.read_buffer_memory(&mut header)?;
+        let next_packet = u16::from(header[0]) | (u16::from(header[1]) << 8);
+        let len = u16::from(header[2]) | (u16::from(header[3]) << 8);
+
+        let n = usize::from(len).min(buf.len());
+        self.read_buffer_memory(&mut buf[..n])?;
+
+        self.next_packet = next_packet;
+        self.write_control_register(ERXRDPTL::ADDR, next_packet as u8)?;
+        self.write_control_register(ERXRDPTH::ADDR, (next_packet >> 8) as u8)?;
+        self.ack_packet()?;
+
+        Ok(len)
+    }
+
+    /// Transmits `bytes` as a single Ethernet frame and blocks until done
+    pub fn transmit(&mut self, bytes: &[u8]) -> Result<(), Error<E>> {
+        let start = self.tx_start;
+        self.write_control_register(ETXSTL::ADDR, start as u8)?;
+        self.write_control_register(ETXSTH::ADDR, (start >> 8) as u8)?;
+        self.write_control_register(EWRPTL::ADDR, start as u8)?;
+        self.write_control_register(EWRPTH::ADDR, (start >> 8) as u8)?;
+
+        // Per-packet control byte; zero selects the MACON3 defaults
+        self.write_buffer_memory(&[0x00])?;
+        self.write_buffer_memory(bytes)?;
+
+        let end = start.wrapping_add(bytes.len() as u16);
+        self.write_control_register(ETXNDL::ADDR, end as u8)?;
+        self.write_control_register(ETXNDH::ADDR, (end >> 8) as u8)?;
+
+        // Clear the previous attempt's flags, so that afterwards `pending`
+        // reports this transmission's outcome
+        self.ack(Events {
+            transmit_done: true,
+            transmit_error: true,
+            ..Events::default()
+        })?;
+        self.bit_field_set(ECON1::ADDR, econ1::TXRTS)?;
+        while self.read_control_register(ECON1::ADDR)? & econ1::TXRTS != 0 {}
+
+        Ok(())
+    }
+
+    /// Reads the current link state from the internal PHY
+    pub fn link_state(&mut self) -> Result<bool, Error<E>> {
+        // PHSTAT2, bit 10 (LSTAT)
+        let phstat2 = self.read_phy_register(0x11)?;
+        Ok(phstat2 & (1 << 10) != 0)
+    }
+
+    fn read_phy_register(&mut self, addr: u8) -> Result<u16, Error<E>> {
+        // MICMD lives in MAC/MII register space, where the Bit Field Set/Clear
+        // SPI commands are not permitted, so write it as a plain control register.
+        self.write_control_register(MIREGADR::ADDR, addr)?;
+        self.write_control_register(MICMD::ADDR, micmd::MIIRD)?;
+        while self.read_control_register(MISTAT::ADDR)? & mistat::BUSY != 0 {}
+        self.write_control_register(MICMD::ADDR, 0)?;
+        let lo = self.read_control_register(MIRDL::ADDR)?;
+        let hi = self.read_control_register(MIRDH::ADDR)?;
+        Ok(u16::from(lo) | (u16::from(hi) << 8))
+    }
+
+    fn soft_reset(&mut self) -> Result<(), Error<E>> {
+        self.bus.write(&[Opcode::SystemResetCommand as u8])?;
+        Ok(())
+    }
+
+    fn set_bank<R: Register>(&mut self, register: &R) -> Result<(), Error<E>> {
+        if let Some(bank) = register.bank() {
+            if bank != self.bank {
+                let bits = match bank {
+                    Bank::Bank0 => 0,
+                    Bank::Bank1 => econ1::BSEL0,
+                    Bank::Bank2 => econ1::BSEL1,
+                    Bank::Bank3 => econ1::BSEL0 | econ1::BSEL1,
+                };
+                self.bit_field_clear(ECON1::ADDR, econ1::BSEL0 | econ1::BSEL1)?;
+                if bits != 0 {
+                    self.bit_field_set(ECON1::ADDR, bits)?;
+                }
+                self.bank = bank;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_buffer_memory(&mut self, buf: &mut [u8]) -> Result<(), Error<E>> {
+        // Opcode and data go in one transaction; ERDPT auto-increments
+        // (ECON2.AUTOINC), so each chunk continues where the last one ended.
+        let mut chunk = [0u8; 65];
+        for part in buf.chunks_mut(64) {
+            chunk[0] = Opcode::ReadBufferMemory as u8;
+            let n = part.len() + 1;
+            self.bus.transfer(&mut chunk[..n])?;
+            part.copy_from_slice(&chunk[1..n]);
+        }
+        Ok(())
+    }
+
+    fn write_buffer_memory(&mut self, bytes: &[u8]) -> Result<(), Error<E>> {
+        // Opcode and data go in one transaction; EWRPT auto-increments
+        let mut chunk = [0u8; 65];
+        chunk[0] = Opcode::WriteBufferMemory as u8;
+        for part in bytes.chunks(64) {
+            chunk[1..=part.len()].copy_from_slice(part);
+            self.bus.write(&chunk[..=part.len()])?;
+        }
+        Ok(())
+    }
+
+    fn read_control_register<R: Register>(&mut self, register: R) -> Result<u8, Error<E>> {
+        self.set_bank(&register)?;
+        let opcode = Opcode::ReadControlRegister as u8 | (register.addr() & 0x1f);
+        if register.is_eth_register() {
+            let mut buf = [opcode, 0];
+            self.bus.transfer(&mut buf)?;
+            Ok(buf[1])
+        } else {
+            // One extra dummy byte is required for MAC/MII registers.
+            let mut buf = [opcode, 0, 0];
+            self.bus.transfer(&mut buf)?;
+            Ok(buf[2])
+        }
+    }
+
+    fn write_control_register<R: Register>(&mut self, register: R, value: u8) -> Result<(), Error<E>> {
+        self.set_bank(&register)?;
+        self.bus
+            .write(&[Opcode::WriteControlRegister as u8 | (register.addr() & 0x1f), value])?;
+        Ok(())
+    }
+
+    fn bit_field_set<R: Register>(&mut self, register: R, mask: u8) -> Result<(), Error<E>> {
+        self.set_bank(&register)?;
+        self.bus
+            .write(&[Opcode::BitFieldSet as u8 | (register.addr() & 0x1f), mask])?;
+        Ok(())
+    }
+
+    fn bit_field_clear<R: Register>(&mut self, register: R, mask: u8) -> Result<(), Error<E>> {
+        self.set_bank(&register)?;
+        self.bus
+            .write(&[Opcode::BitFieldClear as u8 | (register.addr() & 0x1f), mask])?;
+        Ok(())
+    }
+
+    /// Releases the [`Bus`], INT pin and RESET pin, for example to hand a
+    /// shared SPI bus to another peripheral between polls of this one
+    pub fn free(self) -> (BUS, INT, RESET) {
+        (self.bus, self.int, self.reset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_any_is_false_by_default() {
+        assert!(!Events::default().any());
+    }
+
+    #[test]
+    fn events_any_is_true_if_any_field_set() {
+        let events = Events {
+            link_changed: true,
+            ..Events::default()
+        };
+        assert!(events.any());
+
+        let events = Events {
+            pending_packets: 1,
+            ..Events::default()
+        };
+        assert!(events.any());
+    }
+
+    #[test]
+    fn interrupt_sources_mask_always_sets_intie() {
+        let sources = InterruptSources {
+            packet: false,
+            link: false,
+            transmit: false,
+            transmit_error: false,
+            receive_error: false,
+            wake_on_lan: false,
+        };
+        assert_eq!(sources.mask(), eie::INTIE);
+    }
+
+    #[test]
+    fn interrupt_sources_mask_ors_in_each_source() {
+        let sources = InterruptSources {
+            packet: true,
+            link: true,
+            transmit: false,
+            transmit_error: false,
+            receive_error: false,
+            wake_on_lan: false,
+        };
+        assert_eq!(sources.mask(), eie::INTIE | eie::PKTIE | eie::LINKIE);
+    }
+}
@@ -0,0 +1,67 @@
+//! Bank 0 registers
+
+use super::{Bank, Register};
+
+macro_rules! bank0_register {
+    ($name:ident, $addr:expr) => {
+        #[doc = "Bank 0 register"]
+        #[derive(Clone, Copy)]
+        pub enum $name {
+            #[doc = "Register address"]
+            ADDR,
+        }
+
+        impl Register for $name {
+            fn addr(&self) -> u8 {
+                $addr
+            }
+
+            fn bank(&self) -> Option<Bank> {
+                Some(Bank::Bank0)
+            }
+
+            fn is_eth_register(&self) -> bool {
+                true
+            }
+        }
+    };
+}
+
+bank0_register!(ERDPTL, 0x00);
+bank0_register!(ERDPTH, 0x01);
+bank0_register!(EWRPTL, 0x02);
+bank0_register!(EWRPTH, 0x03);
+bank0_register!(ETXSTL, 0x04);
+bank0_register!(ETXSTH, 0x05);
+bank0_register!(ETXNDL, 0x06);
+bank0_register!(ETXNDH, 0x07);
+bank0_register!(ERXSTL, 0x08);
+bank0_register!(ERXSTH, 0x09);
+bank0_register!(ERXNDL, 0x0a);
+bank0_register!(ERXNDH, 0x0b);
+bank0_register!(ERXRDPTL, 0x0c);
+bank0_register!(ERXRDPTH, 0x0d);
+bank0_register!(ERXWRPTL, 0x0e);
+bank0_register!(ERXWRPTH, 0x0f);
+bank0_register!(ERXFCON, 0x18);
+bank0_register!(EPKTCNT, 0x19);
+
+/// `ERXFCON` bit masks
+pub mod erxfcon {
+    /// Unicast filter enable; frames whose destination matches `MAADR` pass
+    pub const UCEN: u8 = 1 << 7;
+    /// AND/OR combining of the enabled filters (1 = AND, 0 = OR)
+    pub const ANDOR: u8 = 1 << 6;
+    /// Require incoming frames to pass the CRC check to be accepted
+    pub const CRCEN: u8 = 1 << 5;
+    /// Pattern match filter enable
+    pub const PMEN: u8 = 1 << 4;
+    /// Magic packet filter enable (Wake-on-LAN)
+    pub const MPEN: u8 = 1 << 3;
+    /// Hash table filter enable
+    pub const HTEN: u8 = 1 << 2;
+    /// Multicast filter enable; any frame with the multicast bit set passes
+    pub const MCEN: u8 = 1 << 1;
+    /// Broadcast filter enable
+    pub const BCEN: u8 = 1 << 0;
+}
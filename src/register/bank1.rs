@@ -0,0 +1,57 @@
+//! Bank 1 registers
+
+use super::{Bank, Register};
+
+macro_rules! bank1_register {
+    ($name:ident, $addr:expr) => {
+        #[doc = "Bank 1 register"]
+        #[derive(Clone, Copy)]
+        pub enum $name {
+            #[doc = "Register address"]
+            ADDR,
+        }
+
+        impl Register for $name {
+            fn addr(&self) -> u8 {
+                $addr
+            }
+
+            fn bank(&self) -> Option<Bank> {
+                Some(Bank::Bank1)
+            }
+
+            fn is_eth_register(&self) -> bool {
+                true
+            }
+        }
+    };
+}
+
+// Hash table used by the hash-filter; EHTn holds bits [8n, 8n+7] of the
+// 64-bit table indexed by bits 28:23 of the CRC-32 of a destination MAC.
+bank1_register!(EHT0, 0x00);
+bank1_register!(EHT1, 0x01);
+bank1_register!(EHT2, 0x02);
+bank1_register!(EHT3, 0x03);
+bank1_register!(EHT4, 0x04);
+bank1_register!(EHT5, 0x05);
+bank1_register!(EHT6, 0x06);
+bank1_register!(EHT7, 0x07);
+
+// Pattern-match filter mask and checksum/offset
+bank1_register!(EPMM0, 0x08);
+bank1_register!(EPMM1, 0x09);
+bank1_register!(EPMM2, 0x0a);
+bank1_register!(EPMM3, 0x0b);
+bank1_register!(EPMM4, 0x0c);
+bank1_register!(EPMM5, 0x0d);
+bank1_register!(EPMM6, 0x0e);
+bank1_register!(EPMM7, 0x0f);
+bank1_register!(EPMCSL, 0x10);
+bank1_register!(EPMCSH, 0x11);
+bank1_register!(EPMOL, 0x14);
+bank1_register!(EPMOH, 0x15);
+
+// Wake-on-LAN interrupt enable/flag registers
+bank1_register!(EWOLIE, 0x16);
+bank1_register!(EWOLIR, 0x17);
@@ -0,0 +1,77 @@
+//! Bank 2 registers (MAC and PHY control)
+
+use super::{Bank, Register};
+
+macro_rules! bank2_register {
+    ($name:ident, $addr:expr, $eth:expr) => {
+        #[doc = "Bank 2 register"]
+        #[derive(Clone, Copy)]
+        pub enum $name {
+            #[doc = "Register address"]
+            ADDR,
+        }
+
+        impl Register for $name {
+            fn addr(&self) -> u8 {
+                $addr
+            }
+
+            fn bank(&self) -> Option<Bank> {
+                Some(Bank::Bank2)
+            }
+
+            fn is_eth_register(&self) -> bool {
+                $eth
+            }
+        }
+    };
+}
+
+bank2_register!(MACON1, 0x00, false);
+bank2_register!(MACON3, 0x02, false);
+bank2_register!(MACON4, 0x03, false);
+bank2_register!(MABBIPG, 0x04, false);
+bank2_register!(MAIPGL, 0x06, false);
+bank2_register!(MAIPGH, 0x07, false);
+bank2_register!(MACLCON1, 0x08, false);
+bank2_register!(MACLCON2, 0x09, false);
+bank2_register!(MAMXFLL, 0x0a, false);
+bank2_register!(MAMXFLH, 0x0b, false);
+bank2_register!(MICMD, 0x12, false);
+bank2_register!(MIREGADR, 0x14, false);
+bank2_register!(MIWRL, 0x16, false);
+bank2_register!(MIWRH, 0x17, false);
+bank2_register!(MIRDL, 0x18, false);
+bank2_register!(MIRDH, 0x19, false);
+
+/// `MACON1` bit masks
+pub mod macon1 {
+    /// MAC receive enable; frames that pass `ERXFCON` are received
+    pub const MARXEN: u8 = 1 << 0;
+    /// Allow MACCON3.PADCFG/CRC frames to be received while paused
+    pub const PASSALL: u8 = 1 << 1;
+    /// Allow flow-control frames generated by this MAC to loop back
+    pub const RXPAUS: u8 = 1 << 2;
+    /// Allow the MAC to transmit pause control frames
+    pub const TXPAUS: u8 = 1 << 3;
+}
+
+/// `MACON3` bit masks
+pub mod macon3 {
+    /// Enable full-duplex operation
+    pub const FULDPX: u8 = 1 << 0;
+    /// Enable frame length checking against `MAMXFL`
+    pub const FRMLNEN: u8 = 1 << 1;
+    /// Enable automatic padding to 60 bytes plus CRC
+    pub const PADCFG0: u8 = 1 << 5;
+    /// Append a valid CRC to all transmitted frames
+    pub const TXCRCEN: u8 = 1 << 4;
+}
+
+/// `MICMD` bit masks
+pub mod micmd {
+    /// Start a single read of `MIREGADR` into `MIRD`
+    pub const MIIRD: u8 = 1 << 0;
+    /// Start continuous reads of `MIREGADR` (used for link polling)
+    pub const MIISCAN: u8 = 1 << 1;
+}
@@ -0,0 +1,49 @@
+//! Bank 3 registers
+
+use super::{Bank, Register};
+
+macro_rules! bank3_register {
+    ($name:ident, $addr:expr, $eth:expr) => {
+        #[doc = "Bank 3 register"]
+        #[derive(Clone, Copy)]
+        pub enum $name {
+            #[doc = "Register address"]
+            ADDR,
+        }
+
+        impl Register for $name {
+            fn addr(&self) -> u8 {
+                $addr
+            }
+
+            fn bank(&self) -> Option<Bank> {
+                Some(Bank::Bank3)
+            }
+
+            fn is_eth_register(&self) -> bool {
+                $eth
+            }
+        }
+    };
+}
+
+bank3_register!(MAADR5, 0x00, false);
+bank3_register!(MAADR6, 0x01, false);
+bank3_register!(MAADR3, 0x02, false);
+bank3_register!(MAADR4, 0x03, false);
+bank3_register!(MAADR1, 0x04, false);
+bank3_register!(MAADR2, 0x05, false);
+bank3_register!(EBSTSD, 0x06, true);
+bank3_register!(EBSTCON, 0x07, true);
+bank3_register!(MISTAT, 0x0a, false);
+bank3_register!(EREVID, 0x12, true);
+bank3_register!(ECOCON, 0x15, true);
+bank3_register!(EFLOCON, 0x17, true);
+bank3_register!(EPAUSL, 0x18, true);
+bank3_register!(EPAUSH, 0x19, true);
+
+/// `MISTAT` bit masks
+pub mod mistat {
+    /// Set while a MAC-to-PHY register access is in progress
+    pub const BUSY: u8 = 1 << 0;
+}
@@ -0,0 +1,190 @@
+//! Registers mapped into every bank
+
+use super::{Bank, Register};
+
+/// Ethernet Interrupt Enable Register
+#[derive(Clone, Copy)]
+pub enum EIE {
+    /// Register address
+    ADDR,
+}
+
+impl Register for EIE {
+    fn addr(&self) -> u8 {
+        0x1b
+    }
+
+    fn bank(&self) -> Option<Bank> {
+        None
+    }
+
+    fn is_eth_register(&self) -> bool {
+        true
+    }
+}
+
+/// `EIE` bit masks
+pub mod eie {
+    /// Interrupt line enable; must be set for the INT pin to be driven
+    pub const INTIE: u8 = 1 << 7;
+    /// PKTIF enable
+    pub const PKTIE: u8 = 1 << 6;
+    /// DMAIF enable
+    pub const DMAIE: u8 = 1 << 5;
+    /// LINKIF enable (PHY interrupt; also requires `PHIE.PLNKIE`)
+    pub const LINKIE: u8 = 1 << 4;
+    /// TXIF enable
+    pub const TXIE: u8 = 1 << 3;
+    /// WOLIF enable
+    pub const WOLIE: u8 = 1 << 2;
+    /// TXERIF enable
+    pub const TXERIE: u8 = 1 << 1;
+    /// RXERIF enable
+    pub const RXERIE: u8 = 1 << 0;
+}
+
+/// Ethernet Interrupt Request (flag) Register
+#[derive(Clone, Copy)]
+pub enum EIR {
+    /// Register address
+    ADDR,
+}
+
+impl Register for EIR {
+    fn addr(&self) -> u8 {
+        0x1c
+    }
+
+    fn bank(&self) -> Option<Bank> {
+        None
+    }
+
+    fn is_eth_register(&self) -> bool {
+        true
+    }
+}
+
+/// `EIR` bit masks
+///
+/// Apart from `PKTIF`, which follows `EPKTCNT`, these are sticky; the driver
+/// must clear them explicitly (typically via [`crate::Enc28j60::ack`]) once
+/// it has handled the condition they report.
+pub mod eir {
+    /// A packet has been received (mirrors `EPKTCNT != 0`)
+    pub const PKTIF: u8 = 1 << 6;
+    /// DMA copy/checksum operation finished
+    pub const DMAIF: u8 = 1 << 5;
+    /// PHY link status changed; cleared by reading `PHIR`
+    pub const LINKIF: u8 = 1 << 4;
+    /// A packet was transmitted, successfully or not
+    pub const TXIF: u8 = 1 << 3;
+    /// A magic-packet pattern matched while `ERXFCON.MPEN` was set
+    pub const WOLIF: u8 = 1 << 2;
+    /// The last transmit attempt failed (collisions, late collision, ...)
+    pub const TXERIF: u8 = 1 << 1;
+    /// A receive error occurred (buffer full, CRC error, ...)
+    pub const RXERIF: u8 = 1 << 0;
+}
+
+/// Ethernet Status Register
+#[derive(Clone, Copy)]
+pub enum ESTAT {
+    /// Register address
+    ADDR,
+}
+
+impl Register for ESTAT {
+    fn addr(&self) -> u8 {
+        0x1d
+    }
+
+    fn bank(&self) -> Option<Bank> {
+        None
+    }
+
+    fn is_eth_register(&self) -> bool {
+        true
+    }
+}
+
+/// `ESTAT` bit masks
+pub mod estat {
+    /// Set once the internal oscillator has stabilized; clear immediately after reset
+    pub const CLKRDY: u8 = 1 << 0;
+    /// Set while the receive logic is still processing a packet
+    pub const RXBUSY: u8 = 1 << 2;
+    /// Set when the last transmit attempt aborted (exceeded retries, etc.)
+    pub const TXABRT: u8 = 1 << 1;
+    /// Set when the last transmit attempt experienced a late collision
+    pub const LATECOL: u8 = 1 << 4;
+}
+
+/// Ethernet Control Register 2
+#[derive(Clone, Copy)]
+pub enum ECON2 {
+    /// Register address
+    ADDR,
+}
+
+impl Register for ECON2 {
+    fn addr(&self) -> u8 {
+        0x1e
+    }
+
+    fn bank(&self) -> Option<Bank> {
+        None
+    }
+
+    fn is_eth_register(&self) -> bool {
+        true
+    }
+}
+
+/// `ECON2` bit masks
+pub mod econ2 {
+    /// Automatic buffer pointer increment enable (DMA/SPI RBM/WBM)
+    pub const AUTOINC: u8 = 1 << 7;
+    /// Power save enable; set to put the chip into low-power mode
+    pub const PWRSV: u8 = 1 << 5;
+    /// Voltage regulator power save enable; safe to clear alongside `PWRSV`
+    pub const VRPS: u8 = 1 << 3;
+    /// Decrement `EPKTCNT`; write one-shot after a packet has been read out
+    pub const PKTDEC: u8 = 1 << 6;
+}
+
+/// Ethernet Control Register 1
+#[derive(Clone, Copy)]
+pub enum ECON1 {
+    /// Register address
+    ADDR,
+}
+
+impl Register for ECON1 {
+    fn addr(&self) -> u8 {
+        0x1f
+    }
+
+    fn bank(&self) -> Option<Bank> {
+        None
+    }
+
+    fn is_eth_register(&self) -> bool {
+        true
+    }
+}
+
+/// `ECON1` bit masks
+pub mod econ1 {
+    /// Bank select, bit 1
+    pub const BSEL1: u8 = 1 << 1;
+    /// Bank select, bit 0
+    pub const BSEL0: u8 = 1 << 0;
+    /// Receive enable
+    pub const RXEN: u8 = 1 << 2;
+    /// Transmit request to send
+    pub const TXRTS: u8 = 1 << 3;
+    /// DMA checksum mode enable; include the running checksum in DMA copies
+    pub const CSUMEN: u8 = 1 << 4;
+    /// DMA start and busy flag
+    pub const DMAST: u8 = 1 << 5;
+}
@@ -0,0 +1,48 @@
+//! Register address/bank bookkeeping
+//!
+//! The ENC28J60 exposes its control registers through four banks selected by
+//! `ECON1.BSEL`. A handful of registers (`EIE`, `EIR`, `ESTAT`, `ECON2` and
+//! `ECON1`) are mapped into every bank so they never require a bank switch.
+//! Each register module below mirrors one bank from the datasheet; the
+//! [`Register`] trait lets the driver ask a register for its address, its
+//! bank and whether it lives in the MAC/MII address space (those reads need
+//! an extra dummy byte).
+
+pub mod bank0;
+pub mod bank1;
+pub mod bank2;
+pub mod bank3;
+pub mod common;
+
+/// One of the four banks selected through `ECON1.BSEL1:0`
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Bank {
+    /// Bank 0
+    Bank0,
+    /// Bank 1
+    Bank1,
+    /// Bank 2
+    Bank2,
+    /// Bank 3
+    Bank3,
+}
+
+/// Common interface to the control registers
+pub trait Register {
+    /// Address of the register
+    fn addr(&self) -> u8;
+
+    /// Bank the register belongs to, if any
+    ///
+    /// Registers that are mapped into every bank (`EIE`, `EIR`, `ESTAT`,
+    /// `ECON2`, `ECON1`) return `None` since no bank switch is required to
+    /// access them.
+    fn bank(&self) -> Option<Bank>;
+
+    /// Whether this is an ETH register, as opposed to one in the MAC or MII
+    /// control register space
+    ///
+    /// Reading a MAC or MII register (`false` here) returns a dummy byte
+    /// before the actual value, unlike the ETH registers.
+    fn is_eth_register(&self) -> bool;
+}
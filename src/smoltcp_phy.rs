@@ -0,0 +1,156 @@
+//! A `smoltcp` `Device` implementation built directly on the chip's FIFO
+//!
+//! Earlier versions of this module copied every frame through one scratch
+//! buffer shared by both the RX and TX token, which served no purpose other
+//! than serializing the two and capped throughput at one in-flight frame.
+//! This implementation instead has each token reach the chip independently
+//! through a shared `RefCell`, and only does the actual Read/Write Buffer
+//! Memory transfer (against `ERDPT`/`EWRPT`, via
+//! [`crate::Enc28j60::receive`]/[`crate::Enc28j60::transmit`]) once the
+//! token is consumed: [`Phy::receive`] only peeks `EPKTCNT` to decide
+//! whether a frame is available, it doesn't touch the FIFO itself. Each
+//! `consume` still copies its frame through one `MAX_FRAME_LEN`-sized stack
+//! buffer, since `smoltcp` hands the token a closure to fill a buffer of a
+//! length it doesn't know until that buffer exists — there's no lower-level
+//! streaming interface to avoid that copy, just the one this module no
+//! longer forces RX and TX to share. Since `EPKTCNT` tracks how many frames
+//! the chip is still holding, calling [`Phy::receive`] repeatedly drains an
+//! entire burst instead of dropping everything past the first packet.
+
+use core::cell::RefCell;
+
+use embedded_hal::digital::v2::{InputPin, OutputPin};
+use smoltcp::phy::{Device, DeviceCapabilities, RxToken, TxToken};
+use smoltcp::time::Instant;
+use smoltcp::Result;
+
+use crate::{Bus, Enc28j60};
+
+/// Largest Ethernet frame this `Device` will receive or transmit, matching
+/// the `MAMXFL` value programmed by [`crate::Enc28j60::new`]
+pub const MAX_FRAME_LEN: usize = 1518;
+
+/// `smoltcp` `Device` wrapper around [`Enc28j60`]
+pub struct Phy<BUS, INT, RESET> {
+    eth: RefCell<Enc28j60<BUS, INT, RESET>>,
+}
+
+impl<BUS, INT, RESET> Phy<BUS, INT, RESET> {
+    /// Wraps `eth`
+    pub fn new(eth: Enc28j60<BUS, INT, RESET>) -> Self {
+        Phy {
+            eth: RefCell::new(eth),
+        }
+    }
+
+    /// Releases the underlying driver
+    pub fn free(self) -> Enc28j60<BUS, INT, RESET> {
+        self.eth.into_inner()
+    }
+}
+
+impl<'a, BUS, INT, RESET, E> Device<'a> for Phy<BUS, INT, RESET>
+where
+    BUS: Bus<Error = E> + 'a,
+    INT: InputPin + 'a,
+    RESET: OutputPin + 'a,
+{
+    type RxToken = Enc28j60RxToken<'a, BUS, INT, RESET>;
+    type TxToken = Enc28j60TxToken<'a, BUS, INT, RESET>;
+
+    fn receive(&'a mut self) -> Option<(Self::RxToken, Self::TxToken)> {
+        if self.eth.get_mut().packet_count().ok()? == 0 {
+            // Nothing queued right now; no FIFO transfer has happened. A
+            // caller wanting to drain a burst just calls `receive` again
+            // later, once more frames have arrived.
+            return None;
+        }
+
+        // Both tokens reach the chip through the same `RefCell`, borrowing
+        // it only for the duration of their own `consume`; `smoltcp` may
+        // call `tx_token.consume` from inside the closure passed to
+        // `rx_token.consume` (e.g. to send an ARP/ICMP reply while
+        // handling the request that prompted it), so the two accesses
+        // must not overlap.
+        Some((
+            Enc28j60RxToken { eth: &self.eth },
+            Enc28j60TxToken { eth: &self.eth },
+        ))
+    }
+
+    fn transmit(&'a mut self) -> Option<Self::TxToken> {
+        Some(Enc28j60TxToken { eth: &self.eth })
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        let mut caps = DeviceCapabilities::default();
+        caps.max_transmission_unit = MAX_FRAME_LEN;
+        // The chip queues received frames faster than one-at-a-time, but
+        // `smoltcp` still processes one ingress frame per `receive()` call;
+        // repeated calls drain the rest of `EPKTCNT`.
+        caps.max_burst_size = None;
+        caps
+    }
+}
+
+/// RX token returned by [`Phy::receive`]
+///
+/// Holds a borrowed handle to the chip rather than a frame: the Read
+/// Buffer Memory transfer that actually pulls the frame out of the receive
+/// FIFO happens lazily, inside [`RxToken::consume`].
+pub struct Enc28j60RxToken<'a, BUS, INT, RESET> {
+    eth: &'a RefCell<Enc28j60<BUS, INT, RESET>>,
+}
+
+impl<'a, BUS, INT, RESET, E> RxToken for Enc28j60RxToken<'a, BUS, INT, RESET>
+where
+    BUS: Bus<Error = E>,
+    INT: InputPin,
+    RESET: OutputPin,
+{
+    fn consume<R, F>(self, _timestamp: Instant, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut [u8]) -> Result<R>,
+    {
+        let mut buf = [0; MAX_FRAME_LEN];
+        // Borrowed only long enough to pull the frame out of the FIFO; `f`
+        // runs with no borrow held, so it's free to consume a `TxToken`
+        // sharing this same `RefCell`.
+        let len = self
+            .eth
+            .borrow_mut()
+            .receive(&mut buf)
+            .map_err(|_| smoltcp::Error::Exhausted)?;
+        f(&mut buf[..usize::from(len)])
+    }
+}
+
+/// TX token returned by [`Phy::receive`] and [`Phy::transmit`]
+///
+/// Like [`Enc28j60RxToken`], the Write Buffer Memory transfer into the
+/// transmit FIFO happens lazily, inside [`TxToken::consume`].
+pub struct Enc28j60TxToken<'a, BUS, INT, RESET> {
+    eth: &'a RefCell<Enc28j60<BUS, INT, RESET>>,
+}
+
+impl<'a, BUS, INT, RESET, E> TxToken for Enc28j60TxToken<'a, BUS, INT, RESET>
+where
+    BUS: Bus<Error = E>,
+    INT: InputPin,
+    RESET: OutputPin,
+{
+    fn consume<R, F>(self, _timestamp: Instant, len: usize, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut [u8]) -> Result<R>,
+    {
+        let mut buf = [0; MAX_FRAME_LEN];
+        let r = f(&mut buf[..len])?;
+        // Borrowed only for the actual transfer, after `f` has already
+        // returned.
+        self.eth
+            .borrow_mut()
+            .transmit(&buf[..len])
+            .map_err(|_| smoltcp::Error::Exhausted)?;
+        Ok(r)
+    }
+}